@@ -32,6 +32,11 @@ const RNG_DISABLE: u32 = 0x0;
 // discard the initial numbers until enough entropy is gathered
 const RNG_WARMUP_COUNT: u32 = 0x40000;
 const RNG_INT_OFF: u32 = 0x1;
+// Entropy credited per 1024 bits returned. The warm-up discard above means
+// the stream can be trusted enough to feed the kernel's entropy pool
+// directly, but we have no independent health testing of the raw output,
+// so we credit well under half rather than claiming near-full (1024) credit.
+const RNG_QUALITY: u16 = 400;
 
 struct BCM2835RNGDev;
 
@@ -39,11 +44,34 @@ struct BCM2835Resources {
     base: IoMem<RNG_REG_SIZE>,
 }
 
+impl BCM2835Resources {
+    // On big-endian MIPS SoCs the RNG is strapped to CPU-native byte
+    // order, so accesses must go through the `__raw_*` helpers (no
+    // byteswap) instead of the normal big-endian-aware ones; see the
+    // `raw_regs` field on `BCM2835General`.
+    fn readl(&self, raw_regs: bool, offset: usize) -> Result<u32> {
+        if raw_regs {
+            self.base.try_raw_readl(offset)
+        } else {
+            self.base.try_readl(offset)
+        }
+    }
+
+    fn writel(&self, raw_regs: bool, value: u32, offset: usize) -> Result {
+        if raw_regs {
+            self.base.try_raw_writel(value, offset)
+        } else {
+            self.base.try_writel(value, offset)
+        }
+    }
+}
+
 struct BCM2835General {
     _dev: device::Device,
     clk: RawSpinLock<Option<clk::EnabledClk>>,
     reset: RawSpinLock<Option<reset::Reset>>,
     mask_interrupts: bool,
+    raw_regs: bool,
 }
 
 type BCM2835Registration = hwrng::Registration<BCM2835RNGDev>;
@@ -52,6 +80,7 @@ type DeviceData = device::Data<BCM2835Registration, BCM2835Resources, BCM2835Gen
 #[vtable]
 impl hwrng::Operations for BCM2835RNGDev {
     type Data = Ref<DeviceData>;
+    const QUALITY: u16 = RNG_QUALITY;
 
     fn read(data: RefBorrow<'_, DeviceData>, buffer: &mut [u8], wait: bool) -> core::result::Result<u32, kernel::Error> {
         let bcm2835 = data.resources().ok_or(ENXIO)?;
@@ -60,7 +89,7 @@ impl hwrng::Operations for BCM2835RNGDev {
         let max_words: usize = buffer.len() / size_of::<u32>();
         let mut num_words: usize;
         
-        while bcm2835.base.try_readl(RNG_STATUS)? >> 24 == 0 {
+        while bcm2835.readl(data.raw_regs, RNG_STATUS)? >> 24 == 0 {
             if !wait {
                 return Ok(0);
             }
@@ -68,13 +97,13 @@ impl hwrng::Operations for BCM2835RNGDev {
             spin_loop();
         }
 
-        num_words = usize::try_from(bcm2835.base.try_readl(RNG_STATUS)? >> 24)?;
+        num_words = usize::try_from(bcm2835.readl(data.raw_regs, RNG_STATUS)? >> 24)?;
         if num_words > max_words {
             num_words = max_words;
         }
 
         for i in 0..num_words {
-            let word = bcm2835.base.try_readl(RNG_DATA)?;
+            let word = bcm2835.readl(data.raw_regs, RNG_DATA)?;
             for j in 0..4 {
                 let byte = (word >> (8 * j)) as u8;
                 buffer[i*4 + j] = byte;
@@ -94,15 +123,15 @@ impl hwrng::Operations for BCM2835RNGDev {
 
         if data.mask_interrupts {
             // mask the interrupt
-            val = bcm2835.base.try_readl(RNG_INT_MASK)?;
+            val = bcm2835.readl(data.raw_regs, RNG_INT_MASK)?;
             val |= RNG_INT_OFF;
-            bcm2835.base.try_writel(val, RNG_INT_MASK)?;
+            bcm2835.writel(data.raw_regs, val, RNG_INT_MASK)?;
         }
 
         // set warm-up count & enable
-        if bcm2835.base.try_readl(RNG_CTRL)? != RNG_ENABLE {
-            bcm2835.base.try_writel(RNG_WARMUP_COUNT, RNG_STATUS)?;
-            bcm2835.base.try_writel(RNG_ENABLE, RNG_CTRL)?;
+        if bcm2835.readl(data.raw_regs, RNG_CTRL)? != RNG_ENABLE {
+            bcm2835.writel(data.raw_regs, RNG_WARMUP_COUNT, RNG_STATUS)?;
+            bcm2835.writel(data.raw_regs, RNG_ENABLE, RNG_CTRL)?;
         }
 
         Ok(())
@@ -111,7 +140,7 @@ impl hwrng::Operations for BCM2835RNGDev {
     fn cleanup(data: Self::Data) {
         // disable rng hardware
         if let Some(bcm2835) = data.resources() {
-            bcm2835.base.writel(RNG_DISABLE, RNG_CTRL);
+            let _ = bcm2835.writel(data.raw_regs, RNG_DISABLE, RNG_CTRL);
         }
 
         // disable clock
@@ -149,11 +178,18 @@ impl platform::Driver for BCM2835RNGDriver {
                 _dev: device::Device::from_dev(dev),
                 // Before using the clock (and the reset),
                 // the driver has to request them at probe execution.
+                //
+                // The clock is only truly optional when it is not described
+                // in the device tree at all (-ENOENT). Any other error,
+                // most notably -EPROBE_DEFER when the clock provider isn't
+                // ready yet, must be propagated so the kernel retries probe
+                // later instead of running the RNG off an unprepared clock.
                 clk: unsafe { RawSpinLock::new(
-                    if let Ok(dev_clk) = dev.clk_get(None) {
-                        Some(dev_clk.prepare_enable()?)
+                    match dev.clk_get(None) {
+                        Ok(dev_clk) => Some(dev_clk.prepare_enable()?),
+                        Err(e) if e == ENOENT => None,
+                        Err(e) => return Err(e),
                     }
-                    else { None }
                 )},
                 reset: unsafe { RawSpinLock::new(
                     dev.reset_control_get_optional_exclusive(None)?
@@ -164,6 +200,13 @@ impl platform::Driver for BCM2835RNGDriver {
                     }
                     else { false }
                 },
+                // On big-endian MIPS the RNG peripheral is strapped to
+                // CPU-native register order for every compatible (it's a
+                // property of the SoC, not of a particular variant), so the
+                // raw, non-byteswapping accessors are needed regardless of
+                // `id_info`. On every other build the normal
+                // big-endian-aware accessors are already correct.
+                raw_regs: cfg!(all(target_endian = "big", target_arch = "mips")),
             },
             "BCM2835RNG::Registration"
         )?;
@@ -180,7 +223,7 @@ impl platform::Driver for BCM2835RNGDriver {
         let data = Ref::<DeviceData>::from(data);
 
         data.registrations().ok_or(ENXIO)?.as_pinned_mut()
-            .register(fmt!("rust_bcm2835_hwrng"), 0, data.clone())?;
+            .register(fmt!("rust_bcm2835_hwrng"), data.clone())?;
 
         pr_info!("BCM2835 RNG Rust driver registered.\n");
 
@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Hardware Random Number Generator framework.
+//!
+//! C header: [`include/linux/hw_random.h`](../../../../include/linux/hw_random.h)
+
+use crate::{
+    bindings,
+    error::from_result,
+    str::CString,
+    types::{ForeignOwnable, Opaque},
+    Result,
+};
+use core::{marker::PhantomData, pin::Pin};
+use macros::vtable;
+
+/// Implemented by drivers that provide a hardware random number generator.
+#[vtable]
+pub trait Operations {
+    /// The pointer type that will be used to hold driver-defined data type.
+    type Data: ForeignOwnable;
+
+    /// Entropy quality, in bits of entropy credited per 1024 bits of data
+    /// returned, reported to the core `hwrng` layer so it knows how much
+    /// (if any) of the output to feed directly into the kernel's entropy
+    /// pool. Defaults to `0` (no credit); a driver that discards warm-up
+    /// samples before `read` returns data may advertise a nonzero value.
+    const QUALITY: u16 = 0;
+
+    /// Reads data from the hwrng device into `buffer`, returning the number
+    /// of bytes read, or `0` if none is ready and `wait` is `false`.
+    fn read(
+        data: <Self::Data as ForeignOwnable>::Borrowed<'_>,
+        buffer: &mut [u8],
+        wait: bool,
+    ) -> Result<u32>;
+
+    /// Initializes the device, called before the first `read`.
+    fn init(data: <Self::Data as ForeignOwnable>::Borrowed<'_>) -> Result;
+
+    /// Cleans up the device, called when it is unregistered.
+    fn cleanup(data: Self::Data);
+}
+
+/// A registration of a `hwrng` device.
+//
+// # Invariants
+//
+// `hwrng` is fully initialized whenever `registered` is `true`.
+pub struct Registration<T: Operations> {
+    hwrng: Opaque<bindings::hwrng>,
+    registered: bool,
+    // Kept alive for as long as `hwrng.name` points into it.
+    name: Option<CString>,
+    _p: PhantomData<T>,
+}
+
+// SAFETY: `Registration` does not expose any of `bindings::hwrng`'s fields
+// that would let it be used to break thread-safety.
+unsafe impl<T: Operations> Sync for Registration<T> {}
+
+impl<T: Operations> Registration<T> {
+    /// Creates a new, unregistered `hwrng` registration.
+    pub fn new() -> Self {
+        Self {
+            hwrng: Opaque::uninit(),
+            registered: false,
+            name: None,
+            _p: PhantomData,
+        }
+    }
+
+    /// Registers the device with the `hwrng` core, advertising
+    /// [`Operations::QUALITY`] as the entropy quality of the data it returns.
+    pub fn register(self: Pin<&mut Self>, name: core::fmt::Arguments<'_>, data: T::Data) -> Result {
+        // SAFETY: We only access `hwrng` through its `Opaque` wrapper below,
+        // and never move out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        if this.registered {
+            return Ok(());
+        }
+
+        let name = CString::try_from_fmt(name)?;
+        let hwrng = this.hwrng.get();
+        // SAFETY: `hwrng` points to valid, if uninitialized, memory for a
+        // `struct hwrng`; zero-filling it first gives every field a valid
+        // bit pattern before we set the ones the core expects us to fill in.
+        unsafe {
+            core::ptr::write_bytes(hwrng, 0, 1);
+            (*hwrng).name = name.as_char_ptr();
+            (*hwrng).quality = T::QUALITY;
+            (*hwrng).read = Some(Self::read_callback);
+            (*hwrng).init = Some(Self::init_callback);
+            (*hwrng).cleanup = Some(Self::cleanup_callback);
+            (*hwrng).priv_ = data.into_foreign() as _;
+        }
+        this.name = Some(name);
+
+        // SAFETY: `hwrng` was fully initialized above.
+        let ret = unsafe { bindings::hwrng_register(hwrng) };
+        if ret != 0 {
+            // SAFETY: `data` was moved into `hwrng->priv_` above and is
+            // reclaimed here since registration failed.
+            unsafe { T::Data::from_foreign(hwrng.read().priv_ as _) };
+            return Err(crate::Error::from_kernel_errno(ret));
+        }
+
+        this.registered = true;
+        Ok(())
+    }
+
+    unsafe extern "C" fn read_callback(
+        rng: *mut bindings::hwrng,
+        data: *mut core::ffi::c_void,
+        max: usize,
+        wait: core::ffi::c_int,
+    ) -> core::ffi::c_int {
+        from_result(|| {
+            // SAFETY: `rng->priv_` holds the `T::Data` stashed in `register`.
+            let devdata = unsafe { T::Data::borrow((*rng).priv_ as _) };
+            // SAFETY: `data`/`max` describe a valid buffer supplied by the core.
+            let buffer = unsafe { core::slice::from_raw_parts_mut(data as *mut u8, max) };
+            Ok(T::read(devdata, buffer, wait != 0)? as core::ffi::c_int)
+        })
+    }
+
+    unsafe extern "C" fn init_callback(rng: *mut bindings::hwrng) -> core::ffi::c_int {
+        from_result(|| {
+            // SAFETY: `rng->priv_` holds the `T::Data` stashed in `register`.
+            let devdata = unsafe { T::Data::borrow((*rng).priv_ as _) };
+            T::init(devdata)?;
+            Ok(0)
+        })
+    }
+
+    unsafe extern "C" fn cleanup_callback(rng: *mut bindings::hwrng) {
+        // SAFETY: `rng->priv_` holds the `T::Data` stashed in `register`,
+        // reclaimed here as the device is being unregistered.
+        let devdata = unsafe { T::Data::from_foreign((*rng).priv_ as _) };
+        T::cleanup(devdata);
+    }
+}
+
+impl<T: Operations> Drop for Registration<T> {
+    fn drop(&mut self) {
+        if self.registered {
+            // SAFETY: `self.hwrng` was registered above, per the type invariant.
+            unsafe { bindings::hwrng_unregister(self.hwrng.get()) };
+        }
+    }
+}
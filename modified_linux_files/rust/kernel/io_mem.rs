@@ -0,0 +1,130 @@
+// SPDX-License-Identifier: GPL-2.0
+
+//! Memory-mapped IO.
+//!
+//! C header: [`include/asm-generic/io.h`](../../../../include/asm-generic/io.h)
+
+use crate::{bindings, prelude::*, Result};
+
+/// Represents a memory resource mapped into the kernel's address space.
+///
+/// Accesses go through the `read*`/`write*` family of helpers, which are
+/// bounds-checked against `SIZE` at every call via the `try_*` variants, or
+/// unconditionally via the panicking ones.
+//
+// # Invariants
+//
+// `ptr` is a non-null pointer obtained from a successful call to `ioremap`
+// (or equivalent), and is valid for reads and writes of `SIZE` bytes for the
+// lifetime of the `IoMem`.
+pub struct IoMem<const SIZE: usize> {
+    ptr: usize,
+}
+
+macro_rules! define_accessor {
+    ($try_read:ident, $try_raw_read:ident, $read:ident, $try_write:ident,
+     $try_raw_write:ident, $write:ident, $c_read:ident, $c_raw_read:ident,
+     $c_write:ident, $c_raw_write:ident, $type_name:ty) => {
+        /// Reads IO data from the given offset, bounds-checked against the size
+        /// of the mapping.
+        pub fn $try_read(&self, offset: usize) -> Result<$type_name> {
+            self.check_offset::<$type_name>(offset)?;
+            // SAFETY: The offset is checked above against the size of the mapping.
+            Ok(unsafe { bindings::$c_read(self.ptr_at(offset)) })
+        }
+
+        /// Reads IO data from the given offset without byte-swapping, for
+        /// devices that are strapped to CPU-native endianness (e.g. BCM5301X
+        /// on big-endian MIPS). Bounds-checked against the size of the
+        /// mapping.
+        pub fn $try_raw_read(&self, offset: usize) -> Result<$type_name> {
+            self.check_offset::<$type_name>(offset)?;
+            // SAFETY: The offset is checked above against the size of the mapping.
+            Ok(unsafe { bindings::$c_raw_read(self.ptr_at(offset)) })
+        }
+
+        /// Reads IO data from the given offset. Unlike
+        #[doc = concat!("[`Self::", stringify!($try_read), "`],")]
+        /// this function does not return an error: it performs the same
+        /// bounds check and panics if `offset` is out of bounds.
+        pub fn $read(&self, offset: usize) -> $type_name {
+            self.$try_read(offset).unwrap()
+        }
+
+        /// Writes IO data to the given offset, bounds-checked against the size
+        /// of the mapping.
+        pub fn $try_write(&self, value: $type_name, offset: usize) -> Result {
+            self.check_offset::<$type_name>(offset)?;
+            // SAFETY: The offset is checked above against the size of the mapping.
+            unsafe { bindings::$c_write(value, self.ptr_at(offset)) };
+            Ok(())
+        }
+
+        /// Writes IO data to the given offset without byte-swapping, for
+        /// devices that are strapped to CPU-native endianness. Bounds-checked
+        /// against the size of the mapping.
+        pub fn $try_raw_write(&self, value: $type_name, offset: usize) -> Result {
+            self.check_offset::<$type_name>(offset)?;
+            // SAFETY: The offset is checked above against the size of the mapping.
+            unsafe { bindings::$c_raw_write(value, self.ptr_at(offset)) };
+            Ok(())
+        }
+
+        /// Writes IO data to the given offset. Unlike
+        #[doc = concat!("[`Self::", stringify!($try_write), "`],")]
+        /// this function does not return an error.
+        pub fn $write(&self, value: $type_name, offset: usize) {
+            self.$try_write(value, offset).unwrap()
+        }
+    };
+}
+
+impl<const SIZE: usize> IoMem<SIZE> {
+    /// Creates a new `IoMem` from a raw, already-mapped pointer.
+    //
+    // # Safety
+    //
+    // Callers must ensure that `ptr` is a non-null pointer obtained from a
+    // successful mapping of at least `SIZE` bytes, and that it remains valid
+    // for the lifetime of the returned `IoMem`.
+    pub unsafe fn new(ptr: usize) -> Self {
+        Self { ptr }
+    }
+
+    fn ptr_at(&self, offset: usize) -> *mut core::ffi::c_void {
+        self.ptr.wrapping_add(offset) as *mut core::ffi::c_void
+    }
+
+    fn check_offset<T>(&self, offset: usize) -> Result {
+        let end = offset.checked_add(core::mem::size_of::<T>()).ok_or(EINVAL)?;
+        if end > SIZE {
+            return Err(EINVAL);
+        }
+        Ok(())
+    }
+
+    define_accessor!(
+        try_readb, try_raw_readb, readb, try_writeb, try_raw_writeb, writeb,
+        readb, __raw_readb, writeb, __raw_writeb, u8
+    );
+    define_accessor!(
+        try_readw, try_raw_readw, readw, try_writew, try_raw_writew, writew,
+        readw, __raw_readw, writew, __raw_writew, u16
+    );
+    define_accessor!(
+        try_readl, try_raw_readl, readl, try_writel, try_raw_writel, writel,
+        readl, __raw_readl, writel, __raw_writel, u32
+    );
+    define_accessor!(
+        try_readq, try_raw_readq, readq, try_writeq, try_raw_writeq, writeq,
+        readq, __raw_readq, writeq, __raw_writeq, u64
+    );
+}
+
+impl<const SIZE: usize> Drop for IoMem<SIZE> {
+    fn drop(&mut self) {
+        // SAFETY: `self.ptr` was created via a successful mapping, per the
+        // type invariant.
+        unsafe { bindings::iounmap(self.ptr as _) };
+    }
+}